@@ -1,12 +1,30 @@
 use std::{collections::LinkedList, sync::Mutex};
 
 use libccanvas::{
-    bindings::{Colour, EventVariant, Subscription},
+    bindings::{Colour, EventVariant, Key, Subscription},
     client::{Client, ClientConfig},
     features::common::Dimension,
 };
+use regex::Regex;
 use serde::{Deserialize, Serialize};
+use syntect::{
+    easy::HighlightLines,
+    highlighting::{Color as SyntectColour, ThemeSet},
+    parsing::SyntaxSet,
+    util::LinesWithEndings,
+};
 use tokio::{sync::OnceCell, task::JoinSet};
+use unicode_width::UnicodeWidthChar;
+
+/// Colour used to highlight search matches; restored to the span's
+/// previous colour (or `Colour::Reset`) once the match ends.
+const SEARCH_HIGHLIGHT: Colour = Colour::Yellow;
+
+/// Theme used to syntax-highlight `AddHighlighted` entries.
+const HIGHLIGHT_THEME: &str = "base16-ocean.dark";
+
+static SYNTAX_SET: OnceCell<SyntaxSet> = OnceCell::const_new();
+static HIGHLIGHT_THEME_SET: OnceCell<ThemeSet> = OnceCell::const_new();
 
 const REQ_TAG: &str = "!scroll-request";
 const RES_TAG: &str = "!scroll-response";
@@ -44,12 +62,15 @@ async fn main() {
                 .unwrap_or(100),
         )
         .unwrap();
+    SYNTAX_SET.set(SyntaxSet::load_defaults_newlines()).unwrap();
+    HIGHLIGHT_THEME_SET.set(ThemeSet::load_defaults()).unwrap();
 
     let ((width, height), _) = tokio::join!(
         CLIENT.get().unwrap().term_size(),
         CLIENT.get().unwrap().subscribe_multiple(vec![
             Subscription::specific_message_tag(REQ_TAG.to_string()),
-            Subscription::ScreenResize
+            Subscription::ScreenResize,
+            Subscription::KeyPress
         ])
     );
 
@@ -72,6 +93,22 @@ async fn main() {
                 render(&mut state, term_size);
                 CLIENT.get().unwrap().renderall().await;
             }
+            EventVariant::KeyPress { key } => {
+                let moved = match key {
+                    Key::PageUp => state.scroll(10, term_size.height),
+                    Key::PageDown => state.scroll(-10, term_size.height),
+                    Key::Up => state.scroll(1, term_size.height),
+                    Key::Down => state.scroll(-1, term_size.height),
+                    Key::Home => state.scroll_to_top(term_size.height),
+                    Key::End => state.scroll_to_bottom(),
+                    _ => false,
+                };
+
+                if moved {
+                    render(&mut state, term_size);
+                    CLIENT.get().unwrap().renderall().await;
+                }
+            }
             EventVariant::Message {
                 content, sender, ..
             } => {
@@ -84,11 +121,13 @@ async fn main() {
 
                 let mut res = Vec::new();
                 let mut updated = false;
+                let mut scrolled = false;
+                let previous_len = state.formatted_cache.len();
 
                 while let Some(req) = reqests.pop_front() {
                     match req.content {
                         ScrollRequestVariant::AddEntry { position, entry } => {
-                            if let Some(uid) = state.add(entry, position) {
+                            if let Some(uid) = state.add(entry, position, term_size.height) {
                                 res.push(ScrollResponse::new(
                                     req.id,
                                     ScrollResponseVariant::Created { uid },
@@ -102,7 +141,7 @@ async fn main() {
                             }
                         }
                         ScrollRequestVariant::RemoveEntry { uid } => {
-                            if state.remove(uid) {
+                            if state.remove(uid, term_size.height) {
                                 res.push(ScrollResponse::new(
                                     req.id,
                                     ScrollResponseVariant::Removed,
@@ -116,7 +155,37 @@ async fn main() {
                             }
                         }
                         ScrollRequestVariant::UpdateEntry { uid, new } => {
-                            if state.update(uid, new) {
+                            if state.update(uid, new, term_size.height) {
+                                res.push(ScrollResponse::new(
+                                    req.id,
+                                    ScrollResponseVariant::Updated,
+                                ));
+                                updated = true
+                            } else {
+                                res.push(ScrollResponse::new(
+                                    req.id,
+                                    ScrollResponseVariant::NotFound,
+                                ));
+                            };
+                        }
+                        ScrollRequestVariant::AddRaw { position, text } => {
+                            if let Some(uid) =
+                                state.add(Entry::from_ansi(&text), position, term_size.height)
+                            {
+                                res.push(ScrollResponse::new(
+                                    req.id,
+                                    ScrollResponseVariant::Created { uid },
+                                ));
+                                updated = true
+                            } else {
+                                res.push(ScrollResponse::new(
+                                    req.id,
+                                    ScrollResponseVariant::NotFound,
+                                ));
+                            }
+                        }
+                        ScrollRequestVariant::UpdateEntryRaw { uid, new } => {
+                            if state.update(uid, Entry::from_ansi(&new), term_size.height) {
                                 res.push(ScrollResponse::new(
                                     req.id,
                                     ScrollResponseVariant::Updated,
@@ -129,6 +198,115 @@ async fn main() {
                                 ));
                             };
                         }
+                        ScrollRequestVariant::Scroll { delta } => {
+                            scrolled |= state.scroll(delta, term_size.height);
+                            res.push(ScrollResponse::new(
+                                req.id,
+                                state.viewport_response(term_size.height),
+                            ));
+                        }
+                        ScrollRequestVariant::ScrollToTop => {
+                            scrolled |= state.scroll_to_top(term_size.height);
+                            res.push(ScrollResponse::new(
+                                req.id,
+                                state.viewport_response(term_size.height),
+                            ));
+                        }
+                        ScrollRequestVariant::ScrollToBottom => {
+                            scrolled |= state.scroll_to_bottom();
+                            res.push(ScrollResponse::new(
+                                req.id,
+                                state.viewport_response(term_size.height),
+                            ));
+                        }
+                        ScrollRequestVariant::AddHighlighted {
+                            position,
+                            text,
+                            syntax,
+                        } => {
+                            if let Some(uid) = state.add(
+                                Entry::from_highlighted(&text, &syntax),
+                                position,
+                                term_size.height,
+                            ) {
+                                res.push(ScrollResponse::new(
+                                    req.id,
+                                    ScrollResponseVariant::Created { uid },
+                                ));
+                                updated = true
+                            } else {
+                                res.push(ScrollResponse::new(
+                                    req.id,
+                                    ScrollResponseVariant::NotFound,
+                                ));
+                            }
+                        }
+                        ScrollRequestVariant::UpdateEntryHighlighted { uid, new, syntax } => {
+                            if state.update(uid, Entry::from_highlighted(&new, &syntax), term_size.height) {
+                                res.push(ScrollResponse::new(
+                                    req.id,
+                                    ScrollResponseVariant::Updated,
+                                ));
+                                updated = true
+                            } else {
+                                res.push(ScrollResponse::new(
+                                    req.id,
+                                    ScrollResponseVariant::NotFound,
+                                ));
+                            };
+                        }
+                        ScrollRequestVariant::Search { pattern, regex } => {
+                            match state.search(&pattern, regex, term_size.height) {
+                                Ok((current, total)) => {
+                                    res.push(ScrollResponse::new(
+                                        req.id,
+                                        ScrollResponseVariant::SearchResult { current, total },
+                                    ));
+                                }
+                                Err(()) => {
+                                    res.push(ScrollResponse::new(
+                                        req.id,
+                                        ScrollResponseVariant::InvalidPattern,
+                                    ));
+                                }
+                            }
+                            updated = true;
+                        }
+                        ScrollRequestVariant::SearchNext => {
+                            match state.search_step(true, term_size.height) {
+                                Some((current, total)) => {
+                                    res.push(ScrollResponse::new(
+                                        req.id,
+                                        ScrollResponseVariant::SearchResult { current, total },
+                                    ));
+                                    scrolled = true;
+                                }
+                                None => res.push(ScrollResponse::new(
+                                    req.id,
+                                    ScrollResponseVariant::NotFound,
+                                )),
+                            }
+                        }
+                        ScrollRequestVariant::SearchPrev => {
+                            match state.search_step(false, term_size.height) {
+                                Some((current, total)) => {
+                                    res.push(ScrollResponse::new(
+                                        req.id,
+                                        ScrollResponseVariant::SearchResult { current, total },
+                                    ));
+                                    scrolled = true;
+                                }
+                                None => res.push(ScrollResponse::new(
+                                    req.id,
+                                    ScrollResponseVariant::NotFound,
+                                )),
+                            }
+                        }
+                        ScrollRequestVariant::SearchClear => {
+                            state.search_clear();
+                            res.push(ScrollResponse::new(req.id, ScrollResponseVariant::Recieved));
+                            updated = true;
+                        }
                         ScrollRequestVariant::Multiple { requests: to_add } => {
                             reqests.extend(to_add.into_iter());
 
@@ -140,7 +318,10 @@ async fn main() {
                 let mut set = JoinSet::new();
 
                 if updated {
-                    state.format(term_size.width);
+                    state.reflow_viewport(previous_len, term_size.height);
+                    render(&mut state, term_size);
+                    set.spawn(CLIENT.get().unwrap().renderall());
+                } else if scrolled {
                     render(&mut state, term_size);
                     set.spawn(CLIENT.get().unwrap().renderall());
                 }
@@ -192,17 +373,14 @@ fn render(state: &mut State, term_size: Dimension) {
         state.format(term_size.width);
     }
 
-    for (y, row) in state
-        .formatted_cache
-        .iter()
-        .skip(
-            state
-                .formatted_cache
-                .len()
-                .saturating_sub(term_size.height as usize),
-        )
-        .enumerate()
-    {
+    let len = state.formatted_cache.len();
+    let height = term_size.height as usize;
+    let start = len
+        .saturating_sub(height)
+        .saturating_sub(state.viewport_offset as usize)
+        .min(len.saturating_sub(height));
+
+    for (y, row) in state.formatted_cache.iter().skip(start).take(height).enumerate() {
         let mut x = 0;
         let mut colour: Option<Colour> = None;
 
@@ -212,6 +390,15 @@ fn render(state: &mut State, term_size: Dimension) {
                 Chunk::Text { value } => {
                     if let Some(colour) = colour.as_ref() {
                         for c in value.chars() {
+                            let width = UnicodeWidthChar::width(c).unwrap_or(0);
+                            if width == 0 {
+                                // `setcharcoloured` only takes a single `char`
+                                // per cell, with no way to attach a combining
+                                // mark to the one already written there — so
+                                // this mark is dropped rather than merged
+                                // onto the preceding cell.
+                                continue;
+                            }
                             CLIENT.get().unwrap().setcharcoloured(
                                 x,
                                 y as u32,
@@ -219,12 +406,19 @@ fn render(state: &mut State, term_size: Dimension) {
                                 *colour,
                                 Colour::Reset,
                             );
-                            x += 1
+                            x += width as u32
                         }
                     } else {
                         for c in value.chars() {
+                            let width = UnicodeWidthChar::width(c).unwrap_or(0);
+                            if width == 0 {
+                                // See the `setcharcoloured` branch above:
+                                // `setchar` has the same one-char-per-cell
+                                // limit, so this mark is dropped, not merged.
+                                continue;
+                            }
                             CLIENT.get().unwrap().setchar(x, y as u32, c);
-                            x += 1
+                            x += width as u32
                         }
                     }
                 }
@@ -243,37 +437,144 @@ enum Chunk {
 }
 
 impl Chunk {
+    /// Display width in terminal columns, not byte or char count: wide CJK
+    /// glyphs count as 2, zero-width/combining marks and control chars as 0.
     pub fn len(&self) -> u32 {
         match self {
             Self::Colour { .. } => 0,
-            Self::Text { value } => value.len() as u32,
+            Self::Text { value } => value
+                .chars()
+                .map(|c| UnicodeWidthChar::width(c).unwrap_or(0) as u32)
+                .sum(),
         }
     }
 
+    /// Keeps the first `length` display columns. If a double-wide glyph
+    /// would straddle the boundary it is dropped and the cell it would have
+    /// occupied is padded with a space so column counts stay exact.
     pub fn truncate(&self, length: u32) -> Self {
         match self {
             Self::Colour { .. } => self.clone(),
-            Self::Text { value } => Self::Text {
-                value: {
-                    let mut value = value.clone();
-                    value.truncate(length as usize);
-                    value
-                },
-            },
+            Self::Text { value } => {
+                let mut out = String::new();
+                let mut width = 0;
+
+                for c in value.chars() {
+                    let this_width = UnicodeWidthChar::width(c).unwrap_or(0) as u32;
+
+                    if width + this_width > length {
+                        if width < length {
+                            out.push(' ');
+                        }
+                        break;
+                    }
+
+                    out.push(c);
+                    width += this_width;
+                }
+
+                Self::Text { value: out }
+            }
         }
     }
 
+    /// Drops the first `length` display columns. As with `truncate`, a
+    /// glyph straddling the boundary is dropped and the remainder is padded
+    /// with a leading space rather than shifted out of column alignment.
     pub fn skip(&self, length: u32) -> Self {
         match self {
             Self::Colour { .. } => self.clone(),
-            Self::Text { value } => Self::Text {
-                value: value.chars().skip(length as usize).collect::<String>(),
+            Self::Text { value } => {
+                let mut chars = value.chars().peekable();
+                let mut width = 0;
+                let mut straddled = false;
+
+                while let Some(&c) = chars.peek() {
+                    if width >= length {
+                        break;
+                    }
+
+                    let this_width = UnicodeWidthChar::width(c).unwrap_or(0) as u32;
+
+                    if width + this_width > length {
+                        straddled = true;
+                        chars.next();
+                        break;
+                    }
+
+                    width += this_width;
+                    chars.next();
+                }
+
+                let mut value: String = chars.collect();
+                if straddled {
+                    value.insert(0, ' ');
+                }
+
+                Self::Text { value }
+            }
+        }
+    }
+}
+
+fn to_ccanvas_colour(colour: SyntectColour) -> Colour {
+    Colour::Rgb {
+        r: colour.r,
+        g: colour.g,
+        b: colour.b,
+    }
+}
+
+/// Translates the `;`-separated parameters of a `CSI ... m` SGR sequence
+/// into `Chunk::Colour`s, pushing one per recognised code and silently
+/// skipping anything unrecognised.
+fn push_sgr_colours(entry: &mut Entry, params: &str) {
+    let codes: Vec<i64> = params
+        .split(';')
+        .map(|param| param.parse().unwrap_or(0))
+        .collect();
+
+    let mut codes = codes.into_iter();
+
+    while let Some(code) = codes.next() {
+        let colour = match code {
+            0 => Some(Colour::Reset),
+            30 | 40 => Some(Colour::Black),
+            31 | 41 => Some(Colour::DarkRed),
+            32 | 42 => Some(Colour::DarkGreen),
+            33 | 43 => Some(Colour::DarkYellow),
+            34 | 44 => Some(Colour::DarkBlue),
+            35 | 45 => Some(Colour::DarkMagenta),
+            36 | 46 => Some(Colour::DarkCyan),
+            37 | 47 => Some(Colour::Grey),
+            90 | 100 => Some(Colour::DarkGrey),
+            91 | 101 => Some(Colour::Red),
+            92 | 102 => Some(Colour::Green),
+            93 | 103 => Some(Colour::Yellow),
+            94 | 104 => Some(Colour::Blue),
+            95 | 105 => Some(Colour::Magenta),
+            96 | 106 => Some(Colour::Cyan),
+            97 | 107 => Some(Colour::White),
+            38 | 48 => match codes.next() {
+                Some(5) => codes.next().map(|n| Colour::AnsiValue(n as u8)),
+                Some(2) => {
+                    let r = codes.next().unwrap_or(0) as u8;
+                    let g = codes.next().unwrap_or(0) as u8;
+                    let b = codes.next().unwrap_or(0) as u8;
+                    Some(Colour::Rgb { r, g, b })
+                }
+                _ => None,
             },
+            _ => None,
+        };
+
+        if let Some(colour) = colour {
+            entry.push(Chunk::Colour { value: colour });
         }
     }
 }
 
-#[derive(Default, Deserialize, Debug)]
+#[derive(Default, Deserialize, Clone, Debug)]
 struct Entry(Vec<Chunk>);
 
 impl Entry {
@@ -318,9 +619,9 @@ impl Entry {
             }
 
             if running_length + this_len > length {
-                new.last_mut()
-                    .unwrap()
-                    .push(chunk.truncate(length - running_length));
+                let consumed = length - running_length;
+
+                new.last_mut().unwrap().push(chunk.truncate(consumed));
 
                 new.push(Self::default());
 
@@ -330,7 +631,7 @@ impl Entry {
                     new.last_mut().unwrap().push(previous_colour.clone());
                 }
 
-                let new_head = chunk.skip(length - running_length);
+                let new_head = chunk.skip(consumed);
                 chunks[cursor] = new_head;
 
                 continue;
@@ -344,6 +645,107 @@ impl Entry {
         new
     }
 
+    /// Parses a string containing ANSI SGR colour escapes (as emitted by
+    /// compilers, loggers, `ls --color`, ...) into an `Entry`. Non-SGR CSI
+    /// sequences are dropped and a bare unterminated `ESC` is kept as
+    /// literal text.
+    pub fn from_ansi(text: &str) -> Self {
+        let mut entry = Entry::default();
+        let mut literal = String::new();
+        let mut chars = text.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            if c != '\u{1b}' || chars.peek() != Some(&'[') {
+                literal.push(c);
+                continue;
+            }
+
+            chars.next(); // consume '['
+
+            let mut params = String::new();
+            let mut terminator = None;
+
+            for next in chars.by_ref() {
+                if next.is_ascii_alphabetic() {
+                    terminator = Some(next);
+                    break;
+                }
+                params.push(next);
+            }
+
+            match terminator {
+                Some('m') => {
+                    if !literal.is_empty() {
+                        entry.push(Chunk::Text {
+                            value: std::mem::take(&mut literal),
+                        });
+                    }
+
+                    push_sgr_colours(&mut entry, &params);
+                }
+                Some(_) => {
+                    // Non-SGR CSI sequences carry nothing we render; drop them.
+                }
+                None => {
+                    literal.push('\u{1b}');
+                    literal.push('[');
+                    literal.push_str(&params);
+                }
+            }
+        }
+
+        if !literal.is_empty() {
+            entry.push(Chunk::Text { value: literal });
+        }
+
+        entry
+    }
+
+    /// Tokenizes `text` against the syntax named/extensioned by `syntax`
+    /// using `syntect`, turning each styled span into a `Chunk::Colour`
+    /// followed by its `Chunk::Text`. Falls back to a single plain
+    /// `Chunk::Text` entry when `syntax` names no known grammar.
+    pub fn from_highlighted(text: &str, syntax: &str) -> Self {
+        let syntax_set = SYNTAX_SET.get().unwrap();
+
+        let Some(syntax_ref) = syntax_set
+            .find_syntax_by_token(syntax)
+            .or_else(|| syntax_set.find_syntax_by_extension(syntax))
+        else {
+            return Self(vec![Chunk::Text {
+                value: text.to_string(),
+            }]);
+        };
+
+        let theme = &HIGHLIGHT_THEME_SET.get().unwrap().themes[HIGHLIGHT_THEME];
+        let mut highlighter = HighlightLines::new(syntax_ref, theme);
+        let mut entry = Entry::default();
+
+        for line in LinesWithEndings::from(text) {
+            let Ok(ranges) = highlighter.highlight_line(line, syntax_set) else {
+                entry.push(Chunk::Text {
+                    value: line.to_string(),
+                });
+                continue;
+            };
+
+            for (style, piece) in ranges {
+                if piece.is_empty() {
+                    continue;
+                }
+
+                entry.push(Chunk::Colour {
+                    value: to_ccanvas_colour(style.foreground),
+                });
+                entry.push(Chunk::Text {
+                    value: piece.to_string(),
+                });
+            }
+        }
+
+        entry
+    }
+
     pub fn split_words(&self) -> Self {
         let mut out = Vec::new();
 
@@ -417,14 +819,198 @@ impl Entry {
 
         new
     }
+
+    /// This entry's text chunks concatenated, ignoring colour chunks, in
+    /// display-column space order (byte order, since chunks are ordered).
+    pub fn plain_text(&self) -> String {
+        self.0
+            .iter()
+            .filter_map(|chunk| match chunk {
+                Chunk::Text { value } => Some(value.as_str()),
+                Chunk::Colour { .. } => None,
+            })
+            .collect()
+    }
+
+    /// Total display width of this entry's text chunks.
+    pub fn width(&self) -> u32 {
+        self.0.iter().map(Chunk::len).sum()
+    }
 }
 
-#[derive(Debug, Default)]
+/// Converts a byte offset into `text` to a display-column offset.
+fn display_column(text: &str, byte_offset: usize) -> u32 {
+    text[..byte_offset]
+        .chars()
+        .map(|c| UnicodeWidthChar::width(c).unwrap_or(0) as u32)
+        .sum()
+}
+
+/// Wraps the `[start, start + len)` column span of `line` with a
+/// `Chunk::Colour` marker, restoring whatever colour was active at that
+/// point (or `Colour::Reset`) immediately after the span.
+fn splice_highlight(line: &Entry, start: u32, len: u32, highlight: Colour) -> Entry {
+    if len == 0 {
+        return Entry(line.0.clone());
+    }
+
+    let mut out = Entry::default();
+    let mut pos = 0;
+    let mut active = Colour::Reset;
+    let mut entered = false;
+    let mut restored = false;
+
+    for chunk in line.0.iter() {
+        if let Chunk::Colour { value } = chunk {
+            active = *value;
+
+            // While inside an open highlight span, native colour changes
+            // must not be emitted — doing so would cut the highlight
+            // short partway through the match. Just track `active` so it
+            // can be restored once the span closes.
+            if !(entered && !restored) {
+                out.push(chunk.clone());
+            }
+
+            continue;
+        }
+
+        let width = chunk.len();
+        let chunk_start = pos;
+        let chunk_end = pos + width;
+        pos = chunk_end;
+
+        if entered && !restored && chunk_start >= start + len {
+            out.push(Chunk::Colour { value: active });
+            restored = true;
+        }
+
+        if chunk_end <= start || chunk_start >= start + len {
+            out.push(chunk.clone());
+            continue;
+        }
+
+        let before = start.saturating_sub(chunk_start);
+        let after = (start + len).saturating_sub(chunk_start).min(width);
+
+        if before > 0 {
+            out.push(chunk.truncate(before));
+        }
+
+        if !entered {
+            out.push(Chunk::Colour { value: highlight });
+            entered = true;
+        }
+
+        out.push(chunk.skip(before).truncate(after - before));
+
+        if after < width {
+            out.push(Chunk::Colour { value: active });
+            restored = true;
+            out.push(chunk.skip(after));
+        }
+    }
+
+    if entered && !restored {
+        out.push(Chunk::Colour { value: active });
+    }
+
+    out
+}
+
+/// A buffered entry alongside its precomputed wrapped lines, so adding,
+/// removing or updating one entry only ever rewraps that entry instead of
+/// the whole buffer.
+#[derive(Debug)]
+struct EntryRecord {
+    uid: u32,
+    source: Entry,
+    wrapped: Vec<Entry>,
+}
+
+impl EntryRecord {
+    fn new(uid: u32, source: Entry, width: u32) -> Self {
+        let mut record = Self {
+            uid,
+            source,
+            wrapped: Vec::new(),
+        };
+        record.rewrap(width);
+        record
+    }
+
+    /// Recomputes `wrapped` from `source` for the given terminal `width`,
+    /// honouring the process-wide `LINE_WRAP`/`WORD_WRAP` settings.
+    fn rewrap(&mut self, width: u32) {
+        self.wrapped = if width == 0 {
+            Vec::new()
+        } else if !LINE_WRAP.get().unwrap() {
+            vec![self.source.truncate(width)]
+        } else if !WORD_WRAP.get().unwrap() {
+            self.source.plain_wrap(width)
+        } else {
+            self.source.word_wrap(width)
+        };
+    }
+}
+
+#[derive(Debug)]
 struct State {
     skip: u32,
-    entries: Vec<(u32, Entry)>,
+    entries: Vec<EntryRecord>,
     formatted_cache: Vec<Entry>,
     formatted_cache_width: u32,
+    /// Lines above the bottom the viewport is currently scrolled by.
+    viewport_offset: u32,
+    /// Whether the viewport is pinned to the tail of the buffer. True
+    /// whenever `viewport_offset` is `0`; once the user scrolls up this
+    /// goes false until they scroll back to the bottom.
+    follow: bool,
+    /// `[start, end)` line ranges into `formatted_cache` for each entry in
+    /// `entries`, recomputed by every full `format`. Per-entry mutations
+    /// only trigger that while a search is active, the sole reader.
+    entry_line_ranges: Vec<(usize, usize)>,
+    search: Option<SearchState>,
+    /// Net line count (insertions minus removals) that have landed strictly
+    /// before the visible window since the last `reflow_viewport` —
+    /// `MAX_ENTRIES` front eviction and any `AddEntry`/`RemoveEntry`
+    /// targeting an index above the current viewport both contribute here.
+    /// Those changes shift where the window's content now lives but don't
+    /// change how many lines sit below it, so `reflow_viewport` needs them
+    /// backed out of the plain length delta to keep the window pinned to
+    /// the same content instead of drifting.
+    lines_before_window_delta: i64,
+}
+
+impl Default for State {
+    fn default() -> Self {
+        Self {
+            skip: 0,
+            entries: Vec::new(),
+            formatted_cache: Vec::new(),
+            formatted_cache_width: 0,
+            viewport_offset: 0,
+            follow: true,
+            entry_line_ranges: Vec::new(),
+            search: None,
+            lines_before_window_delta: 0,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct SearchState {
+    matches: Vec<SearchMatch>,
+    current: usize,
+    pattern: String,
+    use_regex: bool,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct SearchMatch {
+    entry_index: usize,
+    column_start: u32,
+    column_len: u32,
 }
 
 static UID: OnceCell<Mutex<u32>> = OnceCell::const_new_with(Mutex::new(0));
@@ -436,36 +1022,98 @@ fn gen_uid() -> u32 {
 }
 
 impl State {
+    /// Full reflow: rewraps every entry and rebuilds `formatted_cache` from
+    /// scratch. Run when `width` actually changes (wrap mode never toggles
+    /// at runtime today) and by the search paths to re-splice highlights,
+    /// including after every `add`/`remove`/`update` while a search is
+    /// active. With no search active, those only rewrap the single
+    /// affected entry.
     pub fn format(&mut self, width: u32) {
         if width == 0 {
             return;
         }
 
         self.formatted_cache_width = width;
-        if !LINE_WRAP.get().unwrap() {
-            self.formatted_cache = self
-                .entries
-                .iter()
-                .map(|(_, entry)| entry.truncate(width))
-                .collect();
-            return;
+        self.formatted_cache.clear();
+
+        for record in self.entries.iter_mut() {
+            record.rewrap(width);
+            self.formatted_cache.extend(record.wrapped.iter().cloned());
         }
 
-        if !WORD_WRAP.get().unwrap() {
-            self.formatted_cache.clear();
-            for (_, entry) in self.entries.iter() {
-                self.formatted_cache.append(&mut entry.plain_wrap(width))
-            }
-            return;
+        self.recompute_entry_line_ranges();
+        self.apply_search_highlight();
+    }
+
+    fn recompute_entry_line_ranges(&mut self) {
+        self.entry_line_ranges.clear();
+        let mut cursor = 0;
+
+        for record in self.entries.iter() {
+            let start = cursor;
+            cursor += record.wrapped.len();
+            self.entry_line_ranges.push((start, cursor));
         }
+    }
 
-        self.formatted_cache.clear();
-        for (_, entry) in self.entries.iter() {
-            self.formatted_cache.append(&mut entry.word_wrap(width))
+    /// Line offset where `entries[index]` starts in `formatted_cache`.
+    /// Appending at the tail (`index == entries.len()`, the steady state
+    /// for a streaming producer) is the offset of the whole cache and
+    /// needs no summation; only an insert/update/remove at an interior
+    /// index has to fall back to re-summing the prefix.
+    fn line_offset_for_index(&self, index: usize) -> usize {
+        if index >= self.entries.len() {
+            return self.formatted_cache.len();
         }
+
+        self.entries[..index]
+            .iter()
+            .map(|record| record.wrapped.len())
+            .sum()
     }
 
-    pub fn add(&mut self, entry: Entry, position: ScrollPosition) -> Option<u32> {
+    /// Splices temporary `Chunk::Colour` markers around matched spans into
+    /// `formatted_cache` without touching the stored `entries`, so a fresh
+    /// `format()` (on `SearchClear`, or a width change) drops them for free.
+    fn apply_search_highlight(&mut self) {
+        let Some(search) = &self.search else {
+            return;
+        };
+        let matches = search.matches.clone();
+
+        for m in matches.iter() {
+            let Some(&(start, end)) = self.entry_line_ranges.get(m.entry_index) else {
+                continue;
+            };
+
+            let mut column = 0;
+
+            for line_idx in start..end {
+                let width = self.formatted_cache[line_idx].width();
+                let line_start = column;
+                let line_end = column + width;
+                column = line_end;
+
+                let local_start = m.column_start.max(line_start) - line_start;
+                let local_end = (m.column_start + m.column_len).min(line_end);
+
+                if local_end <= line_start + local_start {
+                    continue;
+                }
+
+                let local_len = local_end - (line_start + local_start);
+
+                self.formatted_cache[line_idx] = splice_highlight(
+                    &self.formatted_cache[line_idx],
+                    local_start,
+                    local_len,
+                    SEARCH_HIGHLIGHT,
+                );
+            }
+        }
+    }
+
+    pub fn add(&mut self, entry: Entry, position: ScrollPosition, height: u32) -> Option<u32> {
         let mut index = position
             .eval(self.entries.len() as u32 + self.skip)
             .min(self.skip + self.entries.len() as u32 + 1);
@@ -478,47 +1126,333 @@ impl State {
         index -= self.skip;
 
         let uid = gen_uid();
+        let index = index as usize;
+        let record = EntryRecord::new(uid, entry, self.formatted_cache_width);
+        let line_at = self.line_offset_for_index(index);
+        let inserted = record.wrapped.len();
+
+        // An insert landing at or above the current window shifts the
+        // window's content down without changing how many lines sit below
+        // it; back that portion out of the length delta `reflow_viewport`
+        // would otherwise apply wholesale.
+        if line_at <= self.window_start(height) {
+            self.lines_before_window_delta += inserted as i64;
+        }
+
+        self.formatted_cache.splice(line_at..line_at, record.wrapped.iter().cloned());
 
-        if self.entries.len() < index as usize {
-            self.entries.push((uid, entry));
+        if self.entries.len() < index {
+            self.entries.push(record);
         } else {
-            self.entries.insert(index as usize, (uid, entry));
+            self.entries.insert(index, record);
         }
 
-        if &self.entries.len() > MAX_ENTRIES.get().unwrap() {
-            self.entries.remove(0);
+        if self.entries.len() > *MAX_ENTRIES.get().unwrap() {
+            let evicted = self.entries.remove(0);
+            self.lines_before_window_delta -= evicted.wrapped.len() as i64;
+            self.formatted_cache.drain(0..evicted.wrapped.len());
             self.skip += 1;
         }
 
+        self.reflow_search_after_splice();
+
         Some(uid)
     }
 
-    pub fn remove(&mut self, id: u32) -> bool {
-        let index = self
-            .entries
-            .iter()
-            .position(|(entry_id, _item)| &id == entry_id);
+    pub fn remove(&mut self, id: u32, height: u32) -> bool {
+        let index = self.entries.iter().position(|record| record.uid == id);
+
+        let Some(index) = index else {
+            return false;
+        };
+
+        let line_at = self.line_offset_for_index(index);
+        let record = self.entries.remove(index);
+        let removed = record.wrapped.len();
+
+        if line_at + removed <= self.window_start(height) {
+            self.lines_before_window_delta -= removed as i64;
+        }
+
+        self.formatted_cache.drain(line_at..line_at + removed);
+
+        self.reflow_search_after_splice();
+
+        true
+    }
+
+    pub fn update(&mut self, id: u32, new: Entry, height: u32) -> bool {
+        let index = self.entries.iter().position(|record| record.uid == id);
+
+        let Some(index) = index else {
+            return false;
+        };
+
+        let line_at = self.line_offset_for_index(index);
+        let old_len = self.entries[index].wrapped.len();
+        let before_window = line_at + old_len <= self.window_start(height);
+
+        let record = &mut self.entries[index];
+        record.source = new;
+        record.rewrap(self.formatted_cache_width);
+        let new_len = record.wrapped.len();
+
+        if before_window {
+            self.lines_before_window_delta += new_len as i64 - old_len as i64;
+        }
+
+        self.formatted_cache
+            .splice(line_at..line_at + old_len, record.wrapped.iter().cloned());
+
+        self.reflow_search_after_splice();
+
+        true
+    }
+
+    /// A splice shifts line numbers and entry indices for every entry after
+    /// the mutated one (insertion, removal, and `MAX_ENTRIES` front
+    /// eviction alike), which would leave `SearchMatch::entry_index`
+    /// pointing at the wrong entry — or out of range — if matches were
+    /// simply kept as-is. Rather than renumber matches in place, re-run the
+    /// scan against the current entries and re-splice highlights; this
+    /// keeps the common, search-free streaming path the cheap incremental
+    /// one this cache exists for.
+    ///
+    /// `entry_line_ranges` only feeds search highlighting/navigation, so
+    /// this (and the `recompute_entry_line_ranges` it triggers via
+    /// `format`) is skipped entirely when no search is active — the common
+    /// case once `MAX_ENTRIES` eviction kicks in under sustained
+    /// throughput.
+    fn reflow_search_after_splice(&mut self) {
+        let Some(search) = &self.search else {
+            return;
+        };
+
+        let pattern = search.pattern.clone();
+        let use_regex = search.use_regex;
+        let previous_current = search.current;
+
+        let Ok(re) = Self::compile_pattern(&pattern, use_regex) else {
+            return;
+        };
+
+        let matches = self.scan_matches(&re);
+        // The match set was just rebuilt from scratch, so there's no
+        // stable identity to track the old current match by; clamp it
+        // into range rather than losing it to an out-of-bounds index.
+        let current = previous_current.min(matches.len().saturating_sub(1));
+
+        self.search = Some(SearchState {
+            matches,
+            current,
+            pattern,
+            use_regex,
+        });
+        self.format(self.formatted_cache_width);
+    }
+
+    fn max_viewport_offset(&self, height: u32) -> u32 {
+        (self.formatted_cache.len() as u32).saturating_sub(height)
+    }
+
+    /// First line (into `formatted_cache`) currently on screen, i.e. the
+    /// start of the visible window, given the buffer's current length.
+    fn window_start(&self, height: u32) -> usize {
+        self.formatted_cache
+            .len()
+            .saturating_sub(height as usize)
+            .saturating_sub(self.viewport_offset as usize)
+    }
+
+    /// Moves the viewport by `delta` lines (positive scrolls back into
+    /// history, negative scrolls toward the tail). Returns whether the
+    /// viewport actually moved.
+    pub fn scroll(&mut self, delta: i32, height: u32) -> bool {
+        let max_offset = self.max_viewport_offset(height);
+        let new_offset = self
+            .viewport_offset
+            .saturating_add_signed(delta)
+            .min(max_offset);
+
+        let moved = new_offset != self.viewport_offset;
+        self.viewport_offset = new_offset;
+        self.follow = self.viewport_offset == 0;
+        moved
+    }
+
+    pub fn scroll_to_top(&mut self, height: u32) -> bool {
+        let max_offset = self.max_viewport_offset(height);
+        let moved = self.viewport_offset != max_offset;
+        self.viewport_offset = max_offset;
+        self.follow = self.viewport_offset == 0;
+        moved
+    }
+
+    pub fn scroll_to_bottom(&mut self) -> bool {
+        let moved = self.viewport_offset != 0;
+        self.viewport_offset = 0;
+        self.follow = true;
+        moved
+    }
+
+    pub fn viewport_response(&self, height: u32) -> ScrollResponseVariant {
+        ScrollResponseVariant::Scrolled {
+            offset: self.viewport_offset,
+            at_top: self.viewport_offset == self.max_viewport_offset(height),
+            at_bottom: self.viewport_offset == 0,
+        }
+    }
+
+    /// Keeps the visible lines in place after a reflow: while the user is
+    /// scrolled away from the bottom, newly added or removed lines must
+    /// shift `viewport_offset` by the same amount so the content under the
+    /// viewport doesn't jump. When following the tail, the viewport simply
+    /// stays pinned at the bottom.
+    ///
+    /// `viewport_offset` counts lines *below* the window, so a plain
+    /// length delta only gets this right when every insertion/removal
+    /// happens at or after the window (e.g. appends past the tail).
+    /// Anything that happens strictly before the window — `MAX_ENTRIES`
+    /// front eviction, or an `AddEntry`/`RemoveEntry`/`UpdateEntry` aimed
+    /// above the current viewport — shifts the window's absolute start but
+    /// leaves the line count below it untouched, so `lines_before_window_delta`
+    /// backs that portion out of the delta rather than letting it fold in.
+    pub fn reflow_viewport(&mut self, previous_len: usize, height: u32) {
+        let before_window = self.lines_before_window_delta;
+        self.lines_before_window_delta = 0;
+
+        if self.follow {
+            self.viewport_offset = 0;
+            return;
+        }
+
+        let new_len = self.formatted_cache.len() as i64;
+        let delta = new_len - previous_len as i64 - before_window;
+
+        self.viewport_offset = (self.viewport_offset as i64 + delta)
+            .max(0)
+            .min(self.max_viewport_offset(height) as i64) as u32;
+        self.follow = self.viewport_offset == 0;
+    }
+
+    /// Scans every entry's concatenated text for matches of `re`, recording
+    /// them in display-column space.
+    fn scan_matches(&self, re: &Regex) -> Vec<SearchMatch> {
+        let mut matches = Vec::new();
 
-        if let Some(index) = index {
-            self.entries.remove(index);
-            true
+        for (entry_index, record) in self.entries.iter().enumerate() {
+            let text = record.source.plain_text();
+
+            for m in re.find_iter(&text) {
+                let column_start = display_column(&text, m.start());
+                let column_len = display_column(&text, m.end()) - column_start;
+
+                matches.push(SearchMatch {
+                    entry_index,
+                    column_start,
+                    column_len,
+                });
+            }
+        }
+
+        matches
+    }
+
+    fn compile_pattern(pattern: &str, use_regex: bool) -> Result<Regex, ()> {
+        if use_regex {
+            Regex::new(pattern)
         } else {
-            false
+            Regex::new(&regex::escape(pattern))
         }
+        .map_err(|_| ())
     }
 
-    pub fn update(&mut self, id: u32, new: Entry) -> bool {
-        let index = self
-            .entries
-            .iter()
-            .position(|(entry_id, _item)| &id == entry_id);
+    /// Compiles `pattern` (as a literal or as a `regex` crate pattern) and
+    /// scans every entry's concatenated text for matches, recording them in
+    /// display-column space. Scrolls the viewport to the first match (if
+    /// any) so the reported `current: 0` is actually on screen, not just
+    /// highlighted. Returns `(current, total)` or `Err(())` if the pattern
+    /// fails to compile.
+    pub fn search(&mut self, pattern: &str, use_regex: bool, height: u32) -> Result<(usize, usize), ()> {
+        let re = Self::compile_pattern(pattern, use_regex)?;
+        let matches = self.scan_matches(&re);
+        let total = matches.len();
+        let first = matches.first().copied();
+
+        self.search = Some(SearchState {
+            matches,
+            current: 0,
+            pattern: pattern.to_string(),
+            use_regex,
+        });
+        // Re-splice highlight markers for the new matches; a full reflow
+        // here is fine, a regex scan over the whole buffer already just
+        // happened above.
+        self.format(self.formatted_cache_width);
+
+        if let Some(first) = first {
+            self.scroll_to_line(self.line_for_match(&first), height);
+        }
 
-        if let Some(index) = index {
-            self.entries[index].1 = new;
-            true
+        Ok((0, total))
+    }
+
+    pub fn search_clear(&mut self) {
+        self.search = None;
+        self.format(self.formatted_cache_width);
+    }
+
+    /// Moves to the next (`forward`) or previous match, scrolling the
+    /// viewport so it is visible. Returns `(current, total)`, `None` if no
+    /// search is active.
+    pub fn search_step(&mut self, forward: bool, height: u32) -> Option<(usize, usize)> {
+        let total = self.search.as_ref()?.matches.len();
+
+        if total == 0 {
+            return Some((0, 0));
+        }
+
+        let search = self.search.as_mut().unwrap();
+        search.current = if forward {
+            (search.current + 1) % total
         } else {
-            false
+            (search.current + total - 1) % total
+        };
+        let current = search.current;
+        let target = search.matches[current];
+
+        self.scroll_to_line(self.line_for_match(&target), height);
+
+        Some((current, total))
+    }
+
+    fn line_for_match(&self, m: &SearchMatch) -> usize {
+        let Some(&(start, end)) = self.entry_line_ranges.get(m.entry_index) else {
+            return 0;
+        };
+
+        let mut column = 0;
+
+        for line_idx in start..end {
+            let width = self.formatted_cache[line_idx].width();
+
+            if m.column_start < column + width || line_idx + 1 == end {
+                return line_idx;
+            }
+
+            column += width;
         }
+
+        start
+    }
+
+    fn scroll_to_line(&mut self, line_idx: usize, height: u32) {
+        let max_offset = self.max_viewport_offset(height);
+        let offset_from_bottom = (self.formatted_cache.len().saturating_sub(1))
+            .saturating_sub(line_idx) as u32;
+
+        self.viewport_offset = offset_from_bottom.min(max_offset);
+        self.follow = self.viewport_offset == 0;
     }
 }
 
@@ -561,6 +1495,41 @@ enum ScrollRequestVariant {
     RemoveEntry { uid: u32 },
     #[serde(rename = "update")]
     UpdateEntry { uid: u32, new: Entry },
+    #[serde(rename = "add raw")]
+    AddRaw {
+        #[serde(flatten)]
+        position: ScrollPosition,
+        text: String,
+    },
+    #[serde(rename = "update raw")]
+    UpdateEntryRaw { uid: u32, new: String },
+    #[serde(rename = "scroll")]
+    Scroll { delta: i32 },
+    #[serde(rename = "scroll to top")]
+    ScrollToTop,
+    #[serde(rename = "scroll to bottom")]
+    ScrollToBottom,
+    #[serde(rename = "search")]
+    Search { pattern: String, regex: bool },
+    #[serde(rename = "search next")]
+    SearchNext,
+    #[serde(rename = "search prev")]
+    SearchPrev,
+    #[serde(rename = "search clear")]
+    SearchClear,
+    #[serde(rename = "add highlighted")]
+    AddHighlighted {
+        #[serde(flatten)]
+        position: ScrollPosition,
+        text: String,
+        syntax: String,
+    },
+    #[serde(rename = "update highlighted")]
+    UpdateEntryHighlighted {
+        uid: u32,
+        new: String,
+        syntax: String,
+    },
     #[serde(rename = "multiple")]
     Multiple { requests: Vec<ScrollRequest> },
 }
@@ -591,6 +1560,16 @@ enum ScrollResponseVariant {
     NotFound,
     #[serde(rename = "recieved")]
     Recieved,
+    #[serde(rename = "scrolled")]
+    Scrolled {
+        offset: u32,
+        at_top: bool,
+        at_bottom: bool,
+    },
+    #[serde(rename = "search result")]
+    SearchResult { current: usize, total: usize },
+    #[serde(rename = "invalid pattern")]
+    InvalidPattern,
     #[serde(rename = "multiple")]
     Multiple { responses: Vec<Self> },
 }